@@ -0,0 +1,128 @@
+use coerce_rt::actor::ActorId;
+use serde_json::Value;
+
+/// A single attenuation rule evaluated against an incoming, already
+/// deserialized message before it is allowed to reach its handler.
+///
+/// Caveats compose: a [`SturdyRef`] can carry any number of them, and every
+/// one must pass for the message to be dispatched.
+#[derive(Clone, Serialize, Deserialize)]
+pub enum Caveat {
+    /// Only messages registered under this handler identifier may be sent.
+    AllowHandler(String),
+    /// Reject messages registered under this handler identifier.
+    DenyHandler(String),
+    /// The named field of the message body (as JSON) must equal `value`.
+    FieldEquals { field: String, value: Value },
+}
+
+impl Caveat {
+    /// Checks this caveat against the handler identifier the message was
+    /// sent through and its deserialized JSON representation.
+    pub fn check(&self, handler_type: &str, message: &Value) -> bool {
+        match self {
+            Caveat::AllowHandler(allowed) => allowed == handler_type,
+            Caveat::DenyHandler(denied) => denied != handler_type,
+            Caveat::FieldEquals { field, value } => {
+                message.get(field).map_or(false, |v| v == value)
+            }
+        }
+    }
+}
+
+/// A capability-style reference to a remote actor: an [`ActorId`] plus the
+/// set of [`Caveat`]s the bearer must satisfy on every send. References are
+/// minted by attenuating an existing `SturdyRef`, so a holder can delegate a
+/// strictly-narrower capability without contacting the owner of the actor.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SturdyRef {
+    pub actor_id: ActorId,
+    pub caveats: Vec<Caveat>,
+}
+
+impl SturdyRef {
+    pub fn new(actor_id: ActorId) -> Self {
+        SturdyRef {
+            actor_id,
+            caveats: vec![],
+        }
+    }
+
+    /// Mints a new reference carrying every caveat of this one plus the
+    /// added restriction of `caveat`. The result is never more permissive
+    /// than `self`.
+    pub fn attenuate(&self, caveat: Caveat) -> SturdyRef {
+        let mut caveats = self.caveats.clone();
+        caveats.push(caveat);
+
+        SturdyRef {
+            actor_id: self.actor_id.clone(),
+            caveats,
+        }
+    }
+
+    /// Evaluates every caveat against the incoming message, returning `true`
+    /// only if all of them are satisfied.
+    pub fn authorize(&self, handler_type: &str, message: &Value) -> bool {
+        self.caveats
+            .iter()
+            .all(|caveat| caveat.check(handler_type, message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn owner_ref_with_no_caveats_authorizes_anything() {
+        let owner_ref = SturdyRef::new(1);
+
+        assert!(owner_ref.authorize("Withdraw", &json!({"amount": 100})));
+    }
+
+    #[test]
+    fn allow_handler_caveat_rejects_other_handlers() {
+        let reader = SturdyRef::new(1).attenuate(Caveat::AllowHandler("GetBalance".to_string()));
+
+        assert!(reader.authorize("GetBalance", &json!({})));
+        assert!(!reader.authorize("Withdraw", &json!({})));
+    }
+
+    #[test]
+    fn deny_handler_caveat_rejects_only_named_handler() {
+        let no_withdraw = SturdyRef::new(1).attenuate(Caveat::DenyHandler("Withdraw".to_string()));
+
+        assert!(!no_withdraw.authorize("Withdraw", &json!({})));
+        assert!(no_withdraw.authorize("GetBalance", &json!({})));
+    }
+
+    #[test]
+    fn field_equals_caveat_checks_deserialized_message_body() {
+        let small_only = SturdyRef::new(1).attenuate(Caveat::FieldEquals {
+            field: "amount".to_string(),
+            value: json!(10),
+        });
+
+        assert!(small_only.authorize("Withdraw", &json!({"amount": 10})));
+        assert!(!small_only.authorize("Withdraw", &json!({"amount": 500})));
+        assert!(!small_only.authorize("Withdraw", &json!({})));
+    }
+
+    #[test]
+    fn attenuation_only_ever_narrows_a_reference() {
+        let owner_ref = SturdyRef::new(1);
+        let narrowed = owner_ref
+            .attenuate(Caveat::AllowHandler("GetBalance".to_string()))
+            .attenuate(Caveat::FieldEquals {
+                field: "amount".to_string(),
+                value: json!(10),
+            });
+
+        assert_eq!(narrowed.caveats.len(), 2);
+        assert!(narrowed.authorize("GetBalance", &json!({"amount": 10})));
+        assert!(!narrowed.authorize("GetBalance", &json!({"amount": 11})));
+        assert!(!narrowed.authorize("Withdraw", &json!({"amount": 10})));
+    }
+}
@@ -0,0 +1,97 @@
+use crate::actor::RemoteHandler;
+use crate::context::RemoteActorContext;
+use crate::debtor::Debtor;
+use crate::handler::{LocalMessageHandler, RemoteActorMessageHandler, RemoteMessageHandler};
+use coerce_rt::actor::context::ActorContext;
+use coerce_rt::actor::message::{Handler, Message};
+use coerce_rt::actor::Actor;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Default outstanding-send ceiling for a context's [`Debtor`], absent an
+/// explicit call to [`RemoteActorContextBuilder::with_debt_ceiling`].
+const DEFAULT_DEBT_CEILING: usize = 1024;
+
+pub struct RemoteActorContextBuilder {
+    inner: Option<ActorContext>,
+    handlers: HashMap<String, Box<dyn RemoteMessageHandler + Send + Sync>>,
+    local_handlers: HashMap<String, Box<dyn LocalMessageHandler + Send + Sync>>,
+    propagate_trace: bool,
+    debt_ceiling: usize,
+}
+
+impl RemoteActorContextBuilder {
+    pub fn new() -> Self {
+        RemoteActorContextBuilder {
+            inner: None,
+            handlers: HashMap::new(),
+            local_handlers: HashMap::new(),
+            propagate_trace: false,
+            debt_ceiling: DEFAULT_DEBT_CEILING,
+        }
+    }
+
+    /// Enables or disables propagation of the caller's `tracing` span
+    /// across node boundaries. Disabled by default so nodes that don't
+    /// use `tracing` don't pay for capturing/serializing span context.
+    pub fn with_trace_propagation(mut self, enabled: bool) -> Self {
+        self.propagate_trace = enabled;
+
+        self
+    }
+
+    /// Sets the outstanding-work ceiling for this context's [`Debtor`]: the
+    /// number of remote sends that may be in flight before `deliver` starts
+    /// awaiting for credit instead of enqueueing unboundedly.
+    pub fn with_debt_ceiling(mut self, ceiling: usize) -> Self {
+        self.debt_ceiling = ceiling;
+
+        self
+    }
+
+    pub fn with_handler<A, M>(mut self, identifier: &str) -> Self
+    where
+        A: 'static + Actor + Handler<M> + Send + Sync,
+        M: 'static + DeserializeOwned + Send + Sync,
+        M::Result: Serialize + Send + Sync,
+    {
+        let ctx = match &self.inner {
+            Some(ctx) => ctx.clone(),
+            None => ActorContext::current_context(),
+        };
+
+        let handler = RemoteActorMessageHandler::<A, M>::new(ctx);
+        let local_handler = RemoteActorMessageHandler::<A, M>::local();
+
+        self.handlers.insert(String::from(identifier), handler);
+        self.local_handlers
+            .insert(String::from(identifier), local_handler);
+
+        self
+    }
+
+    pub fn with_actor_context(mut self, ctx: ActorContext) -> Self {
+        self.inner = Some(ctx.clone());
+
+        self
+    }
+
+    pub async fn build(self) -> RemoteActorContext {
+        let mut inner = match self.inner {
+            Some(ctx) => ctx,
+            None => ActorContext::current_context(),
+        };
+
+        let handler_ref =
+            RemoteHandler::new(&mut inner, self.handlers, self.local_handlers).await;
+
+        RemoteActorContext {
+            inner,
+            handler_ref,
+            propagate_trace: self.propagate_trace,
+            debtor: Debtor::new(self.debt_ceiling),
+            current_trace: None,
+        }
+    }
+}
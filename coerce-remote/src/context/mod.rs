@@ -1,9 +1,15 @@
-use crate::actor::{GetHandler, HandlerName, RemoteHandler};
+use crate::actor::{GetHandler, GetLocalHandler, HandlerName, RemoteHandler};
+use crate::capability::SturdyRef;
 use crate::codec::RemoteHandlerMessage;
+use crate::debtor::Debtor;
+use crate::handler::LocalDispatcher;
+use crate::trace::TraceContext;
 use coerce_rt::actor::context::ActorContext;
-use coerce_rt::actor::message::Message;
+use coerce_rt::actor::message::{Handler, Message};
 use coerce_rt::actor::{Actor, ActorId, ActorRef};
+use serde::de::DeserializeOwned;
 use serde::Serialize;
+use tracing::Instrument;
 
 use crate::actor::message::{GetHandler, HandlerName};
 use crate::context::builder::RemoteActorContextBuilder;
@@ -14,37 +20,134 @@ pub mod builder;
 pub struct RemoteActorContext {
     inner: ActorContext,
     handler_ref: ActorRef<RemoteHandler>,
+    propagate_trace: bool,
+    debtor: Debtor,
+    /// The [`TraceContext`] this context is currently dispatching under, if
+    /// any — set only for the duration of [`handle`](Self::handle) and
+    /// `None` otherwise. `handle` holds `&mut self` for that whole time, so
+    /// [`create_message`](Self::create_message) on this context never runs
+    /// while it is set: it always passes `None` to [`TraceContext::capture`],
+    /// and an onward send starts a new `trace_id` rather than inheriting the
+    /// incoming one.
+    current_trace: Option<TraceContext>,
 }
 
 impl RemoteActorContext {
     pub fn builder() -> RemoteActorContextBuilder {
         RemoteActorContextBuilder::new()
     }
+
+    /// The debtor tracking this context's outstanding remote sends, for
+    /// exposing per-node debt metrics alongside the existing host stats.
+    pub fn debtor(&self) -> &Debtor {
+        &self.debtor
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub enum RemoteActorError {
     ActorUnavailable,
+    Unauthorized,
+}
+
+/// Outcome of resolving how to deliver a message via
+/// [`RemoteActorContext::create_message`].
+pub enum Dispatch<M: Message> {
+    /// `actor_ref` resolved to a local handler; the message was already
+    /// delivered straight to its mailbox, bypassing serde entirely.
+    Delivered(M::Result),
+    /// `actor_ref` is remote (or no local-dispatch closure was registered
+    /// for this identifier); here is the envelope to serialize and send
+    /// over the byte-encoded path.
+    Remote(RemoteHandlerMessage<M>),
 }
 
 impl RemoteActorContext {
-    pub async fn handle(
+    /// Handles an incoming, already-encoded message for `identifier` /
+    /// `actor_id`. When `trace_context` is `Some` and trace propagation is
+    /// enabled on this context, the dispatch runs instrumented under a child
+    /// span of the caller's, so logs emitted by the handler carry the
+    /// remote parent context.
+    ///
+    /// Deliberately not `pub`: every dispatch — including the context's own
+    /// full-authority sends — must go through [`handle_attenuated`](Self::handle_attenuated)'s
+    /// caveat check. There is no unchecked path for an external caller to
+    /// reach a handler directly.
+    pub(crate) async fn handle(
         &mut self,
         identifier: String,
         actor_id: ActorId,
         buffer: &[u8],
+        trace_context: Option<TraceContext>,
     ) -> Result<Vec<u8>, RemoteActorError> {
-        let (tx, rx) = tokio::sync::oneshot::channel();
-        let handler = self.handler_ref.send(GetHandler(identifier)).await;
-
-        if let Ok(Some(handler)) = handler {
-            handler.handle(actor_id, buffer, tx).await;
+        let span = match (&trace_context, self.propagate_trace) {
+            (Some(ctx), true) => ctx.child_span(&identifier),
+            _ => tracing::trace_span!("remote_handle", handler = %identifier),
         };
 
-        match rx.await {
-            Ok(res) => Ok(res),
-            Err(_e) => Err(RemoteActorError::ActorUnavailable),
+        // Record the trace this hop is dispatching under for the duration
+        // of the call, restoring the previous value afterwards. Nothing else
+        // can use this context while the call holds `&mut self`, so onward
+        // sends made by the handler do not see it (see `current_trace`).
+        let previous_trace = std::mem::replace(&mut self.current_trace, trace_context.clone());
+
+        let handler_ref = self.handler_ref.clone();
+
+        // `span.enter()` is unsound to hold across an `.await`: the
+        // executor can suspend and resume this task, or interleave it with
+        // another task on the same thread, corrupting span attribution.
+        // `Instrument::instrument` enters the span only for the duration
+        // each poll actually runs, which is the safe way to carry a span
+        // across awaits.
+        let result = async move {
+            let (tx, rx) = tokio::sync::oneshot::channel();
+            let handler = handler_ref.send(GetHandler(identifier)).await;
+
+            if let Ok(Some(handler)) = handler {
+                handler.handle(actor_id, buffer, tx).await;
+            };
+
+            match rx.await {
+                Ok(res) => Ok(res),
+                Err(_e) => Err(RemoteActorError::ActorUnavailable),
+            }
         }
+        .instrument(span)
+        .await;
+
+        self.current_trace = previous_trace;
+
+        result
+    }
+
+    /// The only public entry point onto the byte-encoded dispatch path.
+    /// `sturdy_ref` is checked against every caveat it carries before the
+    /// message is allowed to reach [`handle`](Self::handle); a failing
+    /// caveat yields [`RemoteActorError::Unauthorized`] instead of
+    /// dispatching. A bearer with full owner authority presents a
+    /// [`SturdyRef::new`] with no caveats, which authorizes unconditionally
+    /// — but even that path runs through this check, so there is no way to
+    /// reach a handler that skips it.
+    pub async fn handle_attenuated(
+        &mut self,
+        sturdy_ref: &SturdyRef,
+        identifier: String,
+        buffer: &[u8],
+        trace_context: Option<TraceContext>,
+    ) -> Result<Vec<u8>, RemoteActorError> {
+        let message = serde_json::from_slice(buffer).map_err(|_e| RemoteActorError::Unauthorized)?;
+
+        if !sturdy_ref.authorize(&identifier, &message) {
+            return Err(RemoteActorError::Unauthorized);
+        }
+
+        self.handle(
+            identifier,
+            sturdy_ref.actor_id.clone(),
+            buffer,
+            trace_context,
+        )
+        .await
     }
 
     pub async fn handler_name<A: Actor, M: Message>(&mut self) -> Option<String>
@@ -59,23 +162,105 @@ impl RemoteActorContext {
             .unwrap()
     }
 
+    /// Resolves how `message` should reach `actor_ref`: delivered straight
+    /// to its mailbox when the handler registry has a local-dispatch
+    /// closure for this `(A, M)` pair and `actor_ref` is local, or encoded
+    /// into the wire envelope for the caller to serialize and send over the
+    /// byte-encoded remote path otherwise.
     pub async fn create_message<A: Actor, M: Message>(
         &mut self,
         actor_ref: &ActorRef<A>,
         message: M,
-    ) -> Option<RemoteHandlerMessage<M>>
+    ) -> Option<Dispatch<M>>
     where
-        A: 'static + Send + Sync,
+        A: 'static + Handler<M> + Send + Sync,
         M: 'static + Serialize + Send + Sync,
         M::Result: Send + Sync,
     {
-        match self.handler_name::<A, M>().await {
-            Some(handler_type) => Some(RemoteHandlerMessage {
-                actor_id: actor_ref.id,
-                handler_type,
-                message,
-            }),
-            None => None,
+        let handler_type = self.handler_name::<A, M>().await?;
+
+        if actor_ref.is_local() {
+            let local_handler = self
+                .handler_ref
+                .send(GetLocalHandler(handler_type.clone()))
+                .await
+                .ok()
+                .flatten();
+
+            if let Some(local_handler) = local_handler {
+                if let Some(dispatcher) = local_handler
+                    .as_any()
+                    .downcast_ref::<LocalDispatcher<A, M>>()
+                {
+                    let local_ref = actor_ref.clone().unwrap_local();
+                    if let Some(result) = dispatcher.dispatch(&local_ref, message).await {
+                        return Some(Dispatch::Delivered(result));
+                    }
+
+                    return None;
+                }
+            }
+        }
+
+        Some(Dispatch::Remote(RemoteHandlerMessage {
+            actor_id: actor_ref.id,
+            handler_type,
+            message,
+            trace_context: if self.propagate_trace {
+                TraceContext::capture(self.current_trace.as_ref())
+            } else {
+                None
+            },
+        }))
+    }
+
+    /// Delivers `message` to `actor_ref` end-to-end: preferring the
+    /// zero-copy local dispatch resolved by [`create_message`](Self::create_message)
+    /// and only falling back to the byte-encoded remote path
+    /// (serialize + [`handle`](Self::handle)) for targets that live on
+    /// another node.
+    pub async fn deliver<A, M>(
+        &mut self,
+        actor_ref: &ActorRef<A>,
+        message: M,
+    ) -> Result<M::Result, RemoteActorError>
+    where
+        A: 'static + Handler<M> + Send + Sync,
+        M: 'static + Serialize + DeserializeOwned + Send + Sync,
+        M::Result: Serialize + DeserializeOwned + Send + Sync,
+    {
+        match self.create_message(actor_ref, message).await {
+            Some(Dispatch::Delivered(result)) => Ok(result),
+            Some(Dispatch::Remote(remote_message)) => {
+                let buffer = serde_json::to_vec(&remote_message.message)
+                    .map_err(|_e| RemoteActorError::ActorUnavailable)?;
+
+                // Charge one unit of debt for the in-flight remote send;
+                // awaits here if this sender has hit its outstanding-work
+                // ceiling rather than letting the receiving `ShardHost` be
+                // flooded. The guard repays the debt as soon as the reply
+                // below completes, whether it succeeds or errors.
+                let debt = self.debtor.borrow().await;
+
+                // Unattenuated: this is the context's own owner-authority
+                // send, not a bearer presenting a narrower capability. It
+                // still has to pass through the caveat check below — there
+                // is no path that reaches `handle` without one.
+                let owner_ref = SturdyRef::new(remote_message.actor_id.clone());
+                let result = self
+                    .handle_attenuated(
+                        &owner_ref,
+                        remote_message.handler_type,
+                        &buffer,
+                        remote_message.trace_context,
+                    )
+                    .await?;
+
+                debt.repaid();
+
+                serde_json::from_slice(&result).map_err(|_e| RemoteActorError::ActorUnavailable)
+            }
+            None => Err(RemoteActorError::ActorUnavailable),
         }
     }
 
@@ -0,0 +1,390 @@
+use coerce_rt::actor::context::ActorContext;
+use coerce_rt::actor::message::{Handler, Message};
+use coerce_rt::actor::{Actor, ActorId, LocalActorRef};
+use std::collections::{HashMap, HashSet};
+
+/// A fact published into a [`Dataspace`] by some owning actor. Assertions
+/// stay live until explicitly retracted, or until the asserting actor is
+/// reported stopped, at which point they are retracted automatically.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Hash)]
+pub struct Assertion {
+    pub id: u64,
+    pub owner: ActorId,
+    pub pattern: String,
+    pub payload: Vec<u8>,
+}
+
+/// Sent to a subscriber when a fact matching one of its [`Interest`]
+/// patterns is asserted.
+#[derive(Clone, Debug)]
+pub struct AssertNotification {
+    pub assertion: Assertion,
+}
+
+impl Message for AssertNotification {
+    type Result = ();
+}
+
+/// Sent to a subscriber when a fact matching one of its [`Interest`]
+/// patterns is retracted, whether explicitly or via [`EntityStopped`].
+#[derive(Clone, Debug)]
+pub struct RetractNotification {
+    pub assertion: Assertion,
+}
+
+impl Message for RetractNotification {
+    type Result = ();
+}
+
+/// Type-erased fact subscriber, so [`Dataspace`] can hold subscribers for
+/// many different actor types without itself being generic. Mirrors the
+/// `LocalMessageHandler` registry pattern in `crate::handler`: the concrete
+/// type is captured once, at [`Interest`] registration time, behind this
+/// trait object.
+#[async_trait]
+trait FactObserver: Send + Sync {
+    async fn notify_assert(&self, assertion: Assertion);
+    async fn notify_retract(&self, assertion: Assertion);
+}
+
+/// A [`FactObserver`] backed by a concrete local subscriber. Notifications
+/// are fire-and-forget: a slow or dead subscriber must never stall the
+/// assert/retract that triggered it, so each send happens on its own
+/// spawned task rather than being awaited inline.
+struct ObserverRef<A: Actor> {
+    actor_ref: LocalActorRef<A>,
+}
+
+#[async_trait]
+impl<A> FactObserver for ObserverRef<A>
+where
+    A: 'static + Handler<AssertNotification> + Handler<RetractNotification> + Send + Sync,
+{
+    async fn notify_assert(&self, assertion: Assertion) {
+        let actor_ref = self.actor_ref.clone();
+        tokio::spawn(async move {
+            let _ = actor_ref.send(AssertNotification { assertion }).await;
+        });
+    }
+
+    async fn notify_retract(&self, assertion: Assertion) {
+        let actor_ref = self.actor_ref.clone();
+        tokio::spawn(async move {
+            let _ = actor_ref.send(RetractNotification { assertion }).await;
+        });
+    }
+}
+
+/// A `Dataspace` is a reactive pub/sub actor modelled on the dataspace
+/// Entity protocol: peers `assert` facts that stay visible to interested
+/// subscribers until `retract`ed, and subscribers are notified as matching
+/// facts come and go.
+pub struct Dataspace {
+    assertions: HashMap<u64, Assertion>,
+    by_owner: HashMap<ActorId, HashSet<u64>>,
+    subscribers: HashMap<String, HashMap<ActorId, Box<dyn FactObserver>>>,
+    next_id: u64,
+}
+
+impl Actor for Dataspace {}
+
+impl Dataspace {
+    pub fn new() -> Self {
+        Dataspace {
+            assertions: HashMap::new(),
+            by_owner: HashMap::new(),
+            subscribers: HashMap::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Subscribers currently interested in `pattern`, notified as matching
+    /// facts are asserted or retracted.
+    pub fn matching_subscribers(&self, pattern: &str) -> impl Iterator<Item = &ActorId> {
+        self.subscribers
+            .get(pattern)
+            .into_iter()
+            .flat_map(|subs| subs.keys())
+    }
+
+    async fn notify_asserted(&self, assertion: &Assertion) {
+        if let Some(subs) = self.subscribers.get(&assertion.pattern) {
+            for observer in subs.values() {
+                observer.notify_assert(assertion.clone()).await;
+            }
+        }
+    }
+
+    async fn notify_retracted(&self, assertion: &Assertion) {
+        if let Some(subs) = self.subscribers.get(&assertion.pattern) {
+            for observer in subs.values() {
+                observer.notify_retract(assertion.clone()).await;
+            }
+        }
+    }
+
+    /// Publishes `pattern`/`payload` owned by `owner`, notifying every
+    /// subscriber interested in `pattern`.
+    pub async fn assert(&mut self, owner: ActorId, pattern: String, payload: Vec<u8>) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let assertion = Assertion {
+            id,
+            owner: owner.clone(),
+            pattern,
+            payload,
+        };
+
+        self.by_owner.entry(owner).or_insert_with(HashSet::new).insert(id);
+        self.notify_asserted(&assertion).await;
+        self.assertions.insert(id, assertion);
+
+        id
+    }
+
+    /// Withdraws a previously asserted fact by its id, notifying every
+    /// subscriber interested in its pattern. Returns `false` if no such
+    /// assertion exists.
+    pub async fn retract(&mut self, assertion_id: u64) -> bool {
+        let assertion = match self.assertions.remove(&assertion_id) {
+            Some(assertion) => assertion,
+            None => return false,
+        };
+
+        if let Some(owned) = self.by_owner.get_mut(&assertion.owner) {
+            owned.remove(&assertion_id);
+        }
+
+        self.notify_retracted(&assertion).await;
+
+        true
+    }
+
+    /// Retracts every assertion owned by `actor_id`, notifying interested
+    /// subscribers for each one.
+    pub async fn entity_stopped(&mut self, actor_id: ActorId) {
+        let owned = match self.by_owner.remove(&actor_id) {
+            Some(owned) => owned,
+            None => return,
+        };
+
+        for id in owned {
+            if let Some(assertion) = self.assertions.remove(&id) {
+                self.notify_retracted(&assertion).await;
+            }
+        }
+    }
+}
+
+/// Publish a fact into the dataspace, owned by `owner` until it is
+/// explicitly retracted (via [`Retract`]) or `owner` is reported stopped.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Assert {
+    pub owner: ActorId,
+    pub pattern: String,
+    pub payload: Vec<u8>,
+}
+
+impl Message for Assert {
+    type Result = u64;
+}
+
+#[async_trait]
+impl Handler<Assert> for Dataspace {
+    async fn handle(&mut self, message: Assert, _ctx: &mut ActorContext) -> u64 {
+        self.assert(message.owner, message.pattern, message.payload)
+            .await
+    }
+}
+
+/// Withdraw a previously asserted fact by its id.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Retract {
+    pub assertion_id: u64,
+}
+
+impl Message for Retract {
+    type Result = bool;
+}
+
+#[async_trait]
+impl Handler<Retract> for Dataspace {
+    async fn handle(&mut self, message: Retract, _ctx: &mut ActorContext) -> bool {
+        self.retract(message.assertion_id).await
+    }
+}
+
+/// Register interest in `pattern`; `actor_ref` will be sent
+/// [`AssertNotification`]/[`RetractNotification`] as matching facts come and
+/// go. Not serializable: subscribers are always local, since a notification
+/// is delivered straight to the subscriber's mailbox rather than encoded
+/// over the wire.
+pub struct Interest<A: Actor> {
+    pub subscriber: ActorId,
+    pub pattern: String,
+    pub actor_ref: LocalActorRef<A>,
+}
+
+impl<A> Message for Interest<A>
+where
+    A: 'static + Send + Sync,
+{
+    type Result = ();
+}
+
+#[async_trait]
+impl<A> Handler<Interest<A>> for Dataspace
+where
+    A: 'static + Handler<AssertNotification> + Handler<RetractNotification> + Send + Sync,
+{
+    async fn handle(&mut self, message: Interest<A>, _ctx: &mut ActorContext) {
+        let observer: Box<dyn FactObserver> = Box::new(ObserverRef {
+            actor_ref: message.actor_ref,
+        });
+
+        self.subscribers
+            .entry(message.pattern)
+            .or_insert_with(HashMap::new)
+            .insert(message.subscriber, observer);
+    }
+}
+
+/// Notification sent by a `ShardHost` (or any owner-tracking component)
+/// when an entity stops, so every assertion it owned is retracted and
+/// interested subscribers are notified.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EntityStopped {
+    pub actor_id: ActorId,
+}
+
+impl Message for EntityStopped {
+    type Result = ();
+}
+
+#[async_trait]
+impl Handler<EntityStopped> for Dataspace {
+    async fn handle(&mut self, message: EntityStopped, _ctx: &mut ActorContext) {
+        self.entity_stopped(message.actor_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use coerce_rt::actor::IntoActor;
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+
+    #[tokio::test]
+    async fn assert_then_retract_by_id() {
+        let mut dataspace = Dataspace::new();
+
+        let id = dataspace.assert(1, "orders.new".to_string(), vec![1, 2, 3]).await;
+        assert!(dataspace.retract(id).await);
+        assert!(!dataspace.retract(id).await);
+    }
+
+    #[tokio::test]
+    async fn entity_stopped_retracts_every_assertion_it_owned() {
+        let mut dataspace = Dataspace::new();
+
+        let a = dataspace.assert(1, "orders.new".to_string(), vec![]).await;
+        let b = dataspace.assert(1, "orders.cancelled".to_string(), vec![]).await;
+        let other = dataspace.assert(2, "orders.new".to_string(), vec![]).await;
+
+        dataspace.entity_stopped(1).await;
+
+        assert!(!dataspace.retract(a).await);
+        assert!(!dataspace.retract(b).await);
+        assert!(dataspace.retract(other).await);
+    }
+
+    #[tokio::test]
+    async fn matching_subscribers_is_empty_without_registered_interest() {
+        let dataspace = Dataspace::new();
+
+        assert_eq!(dataspace.matching_subscribers("orders.new").count(), 0);
+    }
+
+    #[derive(Clone, Default)]
+    struct Received {
+        asserted: Arc<Mutex<Vec<Assertion>>>,
+        retracted: Arc<Mutex<Vec<Assertion>>>,
+    }
+
+    struct TestSubscriber {
+        received: Received,
+    }
+
+    impl Actor for TestSubscriber {}
+
+    #[async_trait]
+    impl Handler<AssertNotification> for TestSubscriber {
+        async fn handle(&mut self, message: AssertNotification, _ctx: &mut ActorContext) {
+            self.received.asserted.lock().await.push(message.assertion);
+        }
+    }
+
+    #[async_trait]
+    impl Handler<RetractNotification> for TestSubscriber {
+        async fn handle(&mut self, message: RetractNotification, _ctx: &mut ActorContext) {
+            self.received.retracted.lock().await.push(message.assertion);
+        }
+    }
+
+    /// Notification delivery is fire-and-forget (see [`ObserverRef`]), so the
+    /// test polls the subscriber's inbox instead of awaiting the send
+    /// directly.
+    async fn poll_until_non_empty(items: &Arc<Mutex<Vec<Assertion>>>) -> Vec<Assertion> {
+        for _ in 0..200 {
+            let guard = items.lock().await;
+            if !guard.is_empty() {
+                return guard.clone();
+            }
+            drop(guard);
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+
+        items.lock().await.clone()
+    }
+
+    #[tokio::test]
+    async fn interested_subscriber_receives_assert_and_retract_notifications() {
+        let mut dataspace = Dataspace::new();
+        let mut ctx = ActorContext::current_context();
+
+        let received = Received::default();
+        let subscriber_ref = TestSubscriber {
+            received: received.clone(),
+        }
+        .into_actor(Some("test-subscriber".to_string()), &ctx)
+        .await
+        .expect("spawn test subscriber");
+
+        dataspace
+            .handle(
+                Interest {
+                    subscriber: 99,
+                    pattern: "orders.new".to_string(),
+                    actor_ref: subscriber_ref,
+                },
+                &mut ctx,
+            )
+            .await;
+
+        let id = dataspace
+            .assert(1, "orders.new".to_string(), vec![1, 2, 3])
+            .await;
+
+        let asserted = poll_until_non_empty(&received.asserted).await;
+        assert_eq!(asserted.len(), 1);
+        assert_eq!(asserted[0].id, id);
+
+        dataspace.retract(id).await;
+
+        let retracted = poll_until_non_empty(&received.retracted).await;
+        assert_eq!(retracted.len(), 1);
+        assert_eq!(retracted[0].id, id);
+    }
+}
@@ -0,0 +1,153 @@
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// Tracks outstanding remote work for a single logical sender (e.g. a node
+/// or a `ShardHost` client) so a fast producer can't flood the receiving
+/// side's mailbox. Each in-flight remote send charges one unit of debt;
+/// the unit is repaid once the receiving node's reply comes back.
+///
+/// When the configured ceiling is reached, [`Debtor::borrow`] awaits
+/// asynchronously until a unit of credit frees up rather than letting the
+/// caller buffer unboundedly.
+#[derive(Clone)]
+pub struct Debtor {
+    credit: Arc<Semaphore>,
+    ceiling: usize,
+}
+
+/// A single unit of outstanding debt. Repays itself (releases the credit
+/// back to the `Debtor`) when dropped, so a repayment can't be forgotten on
+/// an error path.
+pub struct DebtGuard {
+    credit: Arc<Semaphore>,
+}
+
+/// A point-in-time snapshot of a [`Debtor`]'s outstanding work, meant to be
+/// merged into a `ShardHost`/node's existing `GetStats` response rather than
+/// queried on its own.
+#[derive(Debug, Eq, PartialEq)]
+pub struct DebtStats {
+    pub ceiling: usize,
+    pub outstanding: usize,
+}
+
+impl Debtor {
+    pub fn new(ceiling: usize) -> Self {
+        Debtor {
+            credit: Arc::new(Semaphore::new(ceiling)),
+            ceiling,
+        }
+    }
+
+    /// The configured outstanding-work ceiling for this debtor.
+    pub fn ceiling(&self) -> usize {
+        self.ceiling
+    }
+
+    /// Units of credit currently available (ceiling minus outstanding debt).
+    pub fn available_credit(&self) -> usize {
+        self.credit.available_permits()
+    }
+
+    /// A snapshot of this debtor's outstanding work, for a `ShardHost` or
+    /// node to fold into its own `GetStats` response alongside shard
+    /// allocation and entity counts.
+    pub fn stats(&self) -> DebtStats {
+        DebtStats {
+            ceiling: self.ceiling,
+            outstanding: self.ceiling - self.available_credit(),
+        }
+    }
+
+    /// Charges one unit of debt, awaiting until credit is available if the
+    /// ceiling has been reached. The returned [`DebtGuard`] repays the debt
+    /// when it is dropped or explicitly [`repaid`](DebtGuard::repaid).
+    pub async fn borrow(&self) -> DebtGuard {
+        let permit = self.credit.clone().acquire_owned().await.expect(
+            "Debtor semaphore should never be closed while the Debtor itself is still alive",
+        );
+
+        // The permit is returned to the semaphore automatically when it is
+        // dropped; we don't hold on to it directly so repayment can be tied
+        // to the oneshot reply completing rather than this future's scope.
+        permit.forget();
+
+        DebtGuard {
+            credit: self.credit.clone(),
+        }
+    }
+}
+
+impl DebtGuard {
+    /// Repays this unit of debt immediately.
+    pub fn repaid(self) {
+        drop(self);
+    }
+}
+
+impl Drop for DebtGuard {
+    fn drop(&mut self) {
+        self.credit.add_permits(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn borrow_consumes_credit_until_repaid() {
+        let debtor = Debtor::new(2);
+        assert_eq!(debtor.available_credit(), 2);
+
+        let debt = debtor.borrow().await;
+        assert_eq!(debtor.available_credit(), 1);
+
+        debt.repaid();
+        assert_eq!(debtor.available_credit(), 2);
+    }
+
+    #[tokio::test]
+    async fn dropping_a_guard_without_repaid_still_returns_credit() {
+        let debtor = Debtor::new(1);
+
+        {
+            let _debt = debtor.borrow().await;
+            assert_eq!(debtor.available_credit(), 0);
+        }
+
+        assert_eq!(debtor.available_credit(), 1);
+    }
+
+    #[tokio::test]
+    async fn borrow_awaits_when_ceiling_is_reached() {
+        let debtor = Debtor::new(1);
+        let first = debtor.borrow().await;
+
+        let debtor_clone = debtor.clone();
+        let second = tokio::time::timeout(
+            std::time::Duration::from_millis(50),
+            debtor_clone.borrow(),
+        )
+        .await;
+        assert!(second.is_err(), "borrow should block while ceiling is reached");
+
+        first.repaid();
+        let second = debtor.borrow().await;
+        second.repaid();
+    }
+
+    #[tokio::test]
+    async fn stats_reports_ceiling_and_outstanding_debt() {
+        let debtor = Debtor::new(5);
+        let _debt = debtor.borrow().await;
+
+        assert_eq!(
+            debtor.stats(),
+            DebtStats {
+                ceiling: 5,
+                outstanding: 1,
+            }
+        );
+    }
+}
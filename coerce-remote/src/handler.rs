@@ -0,0 +1,117 @@
+use coerce_rt::actor::context::ActorContext;
+use coerce_rt::actor::message::{Handler, Message};
+use coerce_rt::actor::{Actor, ActorId, LocalActorRef};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::any::{Any, TypeId};
+use std::marker::PhantomData;
+use tokio::sync::oneshot;
+
+/// Type-erased handler for the byte-encoded remote path: deserializes the
+/// wire buffer into `M`, dispatches it to the actor via `Handler<M>`, and
+/// serializes the result back onto the reply channel.
+#[async_trait]
+pub trait RemoteMessageHandler {
+    fn id(&self) -> TypeId;
+
+    async fn handle(&self, actor_id: ActorId, buffer: &[u8], res: oneshot::Sender<Vec<u8>>);
+}
+
+/// Type-erased handler for the zero-copy local path: delivers an
+/// already-typed `M` straight to a resolved `LocalActorRef<A>`'s mailbox,
+/// without touching serde or the byte buffer at all. Registered in the
+/// handler registry under the same identifier as its `RemoteMessageHandler`
+/// counterpart (see `RemoteActorContextBuilder::with_handler`); callers
+/// that already know the concrete `(A, M)` pair downcast back to
+/// `LocalDispatcher<A, M>` via `as_any` before dispatching.
+pub trait LocalMessageHandler: Any + Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+}
+
+pub struct RemoteActorMessageHandler<A: Actor, M: Message> {
+    ctx: ActorContext,
+    _marker: PhantomData<(A, M)>,
+}
+
+impl<A, M> RemoteActorMessageHandler<A, M>
+where
+    A: 'static + Handler<M> + Send + Sync,
+    M: 'static + DeserializeOwned + Send + Sync,
+    M::Result: Serialize + Send + Sync,
+{
+    pub fn new(ctx: ActorContext) -> Box<dyn RemoteMessageHandler + Send + Sync> {
+        Box::new(RemoteActorMessageHandler {
+            ctx,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Builds this handler pair's local-delivery counterpart: a
+    /// downcastable closure that skips serde entirely when the target
+    /// actor resolves to a `LocalActorRef<A>`.
+    pub fn local() -> Box<dyn LocalMessageHandler + Send + Sync> {
+        Box::new(LocalDispatcher::<A, M> {
+            _marker: PhantomData,
+        })
+    }
+}
+
+#[async_trait]
+impl<A, M> RemoteMessageHandler for RemoteActorMessageHandler<A, M>
+where
+    A: 'static + Handler<M> + Send + Sync,
+    M: 'static + DeserializeOwned + Send + Sync,
+    M::Result: Serialize + Send + Sync,
+{
+    fn id(&self) -> TypeId {
+        TypeId::of::<(A, M)>()
+    }
+
+    async fn handle(&self, actor_id: ActorId, buffer: &[u8], res: oneshot::Sender<Vec<u8>>) {
+        let message: M = match serde_json::from_slice(buffer) {
+            Ok(message) => message,
+            Err(_e) => return,
+        };
+
+        let actor_ref = match self.ctx.clone().get_local_actor::<A>(actor_id).await {
+            Some(actor_ref) => actor_ref,
+            None => return,
+        };
+
+        if let Ok(result) = actor_ref.send(message).await {
+            if let Ok(bytes) = serde_json::to_vec(&result) {
+                let _ = res.send(bytes);
+            }
+        }
+    }
+}
+
+/// The concrete, downcastable local-delivery closure for a single
+/// `(Actor, Message)` pair, minted by [`RemoteActorMessageHandler::local`].
+pub struct LocalDispatcher<A: Actor, M: Message> {
+    _marker: PhantomData<(A, M)>,
+}
+
+impl<A, M> LocalDispatcher<A, M>
+where
+    A: 'static + Handler<M> + Send + Sync,
+    M: 'static + Send + Sync,
+    M::Result: Send + Sync,
+{
+    /// Delivers `message` straight to `actor_ref`'s mailbox via
+    /// `Handler<M>`, skipping serde and the byte-encoded path entirely.
+    pub async fn dispatch(&self, actor_ref: &LocalActorRef<A>, message: M) -> Option<M::Result> {
+        actor_ref.send(message).await.ok()
+    }
+}
+
+impl<A, M> LocalMessageHandler for LocalDispatcher<A, M>
+where
+    A: 'static + Handler<M> + Send + Sync,
+    M: 'static + Send + Sync,
+    M::Result: Send + Sync,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -0,0 +1,47 @@
+/// A serializable snapshot of a `tracing` span's identity, carried across
+/// the wire so that a span started on the sending node can be re-entered as
+/// a child span on the node that actually processes the message.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TraceContext {
+    pub trace_id: String,
+    pub parent_id: String,
+}
+
+impl TraceContext {
+    /// Captures the currently active span, if any, as a `TraceContext`
+    /// suitable for embedding in a [`RemoteHandlerMessage`](crate::codec::RemoteHandlerMessage).
+    ///
+    /// `current` is the [`TraceContext`] the hop making this call was itself
+    /// dispatched under, if any (see `RemoteActorContext`'s in-flight trace
+    /// state). Its `trace_id` is carried forward unchanged so every hop of a
+    /// request shares one `trace_id`; only `parent_id` advances to this
+    /// span's own id, so each hop still points at its immediate caller.
+    /// With no inherited context, this hop starts a new trace and is its own
+    /// parent.
+    pub fn capture(current: Option<&TraceContext>) -> Option<Self> {
+        let span = tracing::Span::current();
+        let id = span.id()?;
+        let parent_id = format!("{:x}", id.into_u64());
+
+        let trace_id = match current {
+            Some(ctx) => ctx.trace_id.clone(),
+            None => parent_id.clone(),
+        };
+
+        Some(TraceContext {
+            trace_id,
+            parent_id,
+        })
+    }
+
+    /// Builds a child span of this context, labelled with the handler it is
+    /// about to dispatch to, so remote logs line up under one trace.
+    pub fn child_span(&self, handler_type: &str) -> tracing::Span {
+        tracing::trace_span!(
+            "remote_handle",
+            handler = %handler_type,
+            trace_id = %self.trace_id,
+            parent_id = %self.parent_id,
+        )
+    }
+}